@@ -1,94 +1,352 @@
-use std::collections::{HashMap};
+use std::collections::HashMap;
+use std::hash::Hash;
 
 /// TrieNode struct represents a node in a Trie data structure
 ///
 /// # Properties
-/// * `is_end` - A boolean value that indicates if the node is the end of a word
-/// * `children` - A HashMap that stores the children of the node
+/// * `value` - The value stored at this node, if it marks the end of a key
+/// * `children` - A HashMap that stores the children of the node, keyed by the next element
 ///
-struct TrieNode {
-    is_end: bool,
-    children: HashMap<String, TrieNode>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "K: serde::Serialize, V: serde::Serialize",
+        deserialize = "K: Eq + std::hash::Hash + serde::Deserialize<'de>, V: serde::Deserialize<'de>"
+    ))
+)]
+struct TrieNode<K, V> {
+    value: Option<V>,
+    children: HashMap<K, TrieNode<K, V>>,
 }
 
-impl TrieNode {
+impl<K, V> TrieNode<K, V>
+where
+    K: Eq + Hash,
+{
     fn new() -> Self {
         TrieNode {
-            is_end: false,
+            value: None,
             children: HashMap::default(),
         }
     }
 }
 
+/// The result of a single-pass [`Trie::lookup`], classifying a key without
+/// requiring a second traversal to tell a missing path from a stored prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchKind {
+    /// The key's path doesn't fully exist in the trie.
+    Miss,
+    /// The key's path exists, but isn't itself a stored key.
+    Prefix,
+    /// The key's path exists and is itself a stored key.
+    Exact,
+}
 
-/// Trie struct represents a Trie data structure
+/// Trie struct represents a Trie (prefix tree) data structure, generic over any
+/// sequence of key elements `K` and an associated value `V`.
+///
+/// # Type Parameters
+/// * `K` - The per-element key type (e.g. `char`, `u8`, `String`), must be `Eq + Hash + Clone`
+/// * `V` - The value associated with a complete key sequence
 ///
 /// # Properties
 /// * `root` - A TrieNode that represents the root of the Trie
 ///
 /// # Methods
 /// * `new` - Creates a new Trie instance
-/// * `insert` - Inserts a word into the Trie
-/// * `contains` - Checks if a word is in the Trie
-/// * `starts_with` - Checks if a word starts with a prefix in the Trie
+/// * `insert` - Inserts a key sequence and its value into the Trie
+/// * `contains` - Checks if a key sequence is in the Trie
+/// * `starts_with` - Checks if a key sequence is a prefix stored in the Trie
+/// * `get` / `get_mut` - Looks up the value associated with a key sequence
+/// * `contains_key` - Alias for `contains`, read as a map-style lookup
+/// * `find_completions` - Collects every stored key under a given prefix (requires `K: Into<char>`)
+/// * `find_longest_prefix` - Finds the longest stored key that prefixes a query (requires `K: Into<char>`)
+/// * `remove` - Deletes a key, pruning any branch left empty behind it
+/// * `search_wildcard` - Matches a pattern where a wildcard element matches any child
+/// * `to_bytes` / `from_bytes` - Persists and reloads the trie (requires the `serde` feature)
+/// * `lookup` - Classifies a key as `Miss`, `Prefix`, or `Exact` in a single pass
 ///
 /// # Examples
 /// ```
-/// use crate::poc_dsa_trie::Trie;
+/// use poc_dsa_trie::Trie;
+///
+/// let mut my_trie: Trie<char, ()> = Trie::new();
+/// my_trie.insert("Hello".chars(), ());
+/// assert_eq!(my_trie.contains("Hello".chars()), true);
+/// assert_eq!(my_trie.starts_with("He".chars()), true);
+/// ```
 ///
-/// let mut my_trie = Trie::new();
-/// my_trie.insert("Hello");
-/// assert_eq!(my_trie.contains("Hello"), true);
-/// assert_eq!(my_trie.starts_with("He"), true);
+/// With the `serde` feature enabled, a trie can be persisted and reloaded instead
+/// of rebuilt from scratch:
 /// ```
-pub struct Trie {
-    root: TrieNode,
+/// # #[cfg(feature = "serde")] {
+/// use poc_dsa_trie::Trie;
+///
+/// let mut my_trie: Trie<char, u32> = Trie::new();
+/// my_trie.insert("Hello".chars(), 1);
+///
+/// let bytes = my_trie.to_bytes().unwrap();
+/// let reloaded: Trie<char, u32> = Trie::from_bytes(&bytes).unwrap();
+/// assert_eq!(reloaded.get("Hello".chars()), Some(&1));
+/// # }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "K: serde::Serialize, V: serde::Serialize",
+        deserialize = "K: Eq + std::hash::Hash + serde::Deserialize<'de>, V: serde::Deserialize<'de>"
+    ))
+)]
+pub struct Trie<K, V> {
+    root: TrieNode<K, V>,
 }
 
-impl Trie {
+impl<K, V> Trie<K, V>
+where
+    K: Eq + Hash + Clone,
+{
     pub fn new() -> Self {
         Trie {
-            root: TrieNode::new()
+            root: TrieNode::new(),
         }
     }
 
-    pub fn insert(&mut self, word: &str) -> String {
+    /// Inserts `key` with the given `value`, returning the value it displaced, if any.
+    pub fn insert(&mut self, key: impl IntoIterator<Item = K>, value: V) -> Option<V> {
         let mut curr = &mut self.root;
 
-        for c in word.chars() {
-            curr = curr.children
-                .entry(c.to_string())
-                .or_insert_with(TrieNode::new)
+        for k in key {
+            curr = curr.children.entry(k).or_insert_with(TrieNode::new)
+        }
+
+        curr.value.replace(value)
+    }
+
+    /// Walks `key` once and classifies the result, avoiding the double traversal
+    /// of calling `contains` and `starts_with` separately.
+    pub fn lookup(&self, key: impl IntoIterator<Item = K>) -> MatchKind {
+        match self.find_node(key) {
+            None => MatchKind::Miss,
+            Some(node) if node.value.is_some() => MatchKind::Exact,
+            Some(_) => MatchKind::Prefix,
+        }
+    }
+
+    pub fn contains(&self, key: impl IntoIterator<Item = K>) -> bool {
+        self.lookup(key) == MatchKind::Exact
+    }
+
+    pub fn starts_with(&self, key: impl IntoIterator<Item = K>) -> bool {
+        self.lookup(key) != MatchKind::Miss
+    }
+
+    /// Returns a reference to the value stored at `key`, if any.
+    pub fn get(&self, key: impl IntoIterator<Item = K>) -> Option<&V> {
+        self.find_node(key).and_then(|node| node.value.as_ref())
+    }
+
+    /// Returns a mutable reference to the value stored at `key`, if any.
+    pub fn get_mut(&mut self, key: impl IntoIterator<Item = K>) -> Option<&mut V> {
+        self.find_node_mut(key).and_then(|node| node.value.as_mut())
+    }
+
+    /// Checks whether `key` has an associated value, equivalent to `contains`.
+    pub fn contains_key(&self, key: impl IntoIterator<Item = K>) -> bool {
+        self.contains(key)
+    }
+
+    /// Removes `key` from the trie, returning `true` if it was present.
+    ///
+    /// Any node that becomes both non-terminal and childless on the way back up
+    /// is pruned, so deleting a key never leaves dead branches behind.
+    pub fn remove(&mut self, key: impl IntoIterator<Item = K>) -> bool {
+        let key: Vec<K> = key.into_iter().collect();
+
+        if !self.contains(key.iter().cloned()) {
+            return false;
+        }
+
+        Self::remove_rec(&mut self.root, &key);
+        true
+    }
+
+    /// Recursively removes `key` from `node`, returning whether `node` itself
+    /// is now empty (non-terminal with no children) and safe for its parent to drop.
+    fn remove_rec(node: &mut TrieNode<K, V>, key: &[K]) -> bool {
+        match key.split_first() {
+            None => {
+                node.value = None;
+            }
+            Some((k, rest)) => {
+                if let Some(child) = node.children.get_mut(k) {
+                    if Self::remove_rec(child, rest) {
+                        node.children.remove(k);
+                    }
+                }
+            }
         }
 
-        curr.is_end = true;
-        word.to_string()
+        node.value.is_none() && node.children.is_empty()
+    }
+
+    /// Searches for `pattern`, where any element accepted by `is_wildcard` matches
+    /// any single child (a "magic dictionary" style lookup).
+    pub fn search_wildcard(
+        &self,
+        pattern: impl IntoIterator<Item = K>,
+        is_wildcard: impl Fn(&K) -> bool,
+    ) -> bool {
+        let pattern: Vec<K> = pattern.into_iter().collect();
+        Self::search_wildcard_rec(&self.root, &pattern, &is_wildcard)
+    }
+
+    fn search_wildcard_rec(
+        node: &TrieNode<K, V>,
+        pattern: &[K],
+        is_wildcard: &impl Fn(&K) -> bool,
+    ) -> bool {
+        match pattern.split_first() {
+            None => node.value.is_some(),
+            Some((k, rest)) => {
+                if is_wildcard(k) {
+                    node.children
+                        .values()
+                        .any(|child| Self::search_wildcard_rec(child, rest, is_wildcard))
+                } else {
+                    match node.children.get(k) {
+                        Some(child) => Self::search_wildcard_rec(child, rest, is_wildcard),
+                        None => false,
+                    }
+                }
+            }
+        }
     }
 
-    pub fn contains(&self, word: &str) -> bool {
+    fn find_node(&self, key: impl IntoIterator<Item = K>) -> Option<&TrieNode<K, V>> {
         let mut curr = &self.root;
 
-        for c in word.chars() {
-            match curr.children.get(&c.to_string()) {
+        for k in key {
+            match curr.children.get(&k) {
                 Some(child) => curr = child,
-                None => return false,
+                None => return None,
             }
         }
 
-        curr.is_end
+        Some(curr)
     }
 
-    pub fn starts_with(&mut self, word: &str) -> bool {
-        let mut curr = &self.root;
+    fn find_node_mut(&mut self, key: impl IntoIterator<Item = K>) -> Option<&mut TrieNode<K, V>> {
+        let mut curr = &mut self.root;
 
-        for c in word.chars() {
-            match curr.children.get(&c.to_string()) {
+        for k in key {
+            match curr.children.get_mut(&k) {
                 Some(child) => curr = child,
-                None => return false,
+                None => return None,
             }
         }
 
-        true
+        Some(curr)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K, V> Trie<K, V>
+where
+    K: Eq + Hash + Clone + serde::Serialize + serde::de::DeserializeOwned,
+    V: serde::Serialize + serde::de::DeserializeOwned,
+{
+    /// Serializes the trie to a JSON byte buffer, for persisting a built dictionary.
+    pub fn to_bytes(&self) -> serde_json::Result<Vec<u8>> {
+        serde_json::to_vec(self)
+    }
+
+    /// Reloads a trie previously written by [`Trie::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> serde_json::Result<Self> {
+        serde_json::from_slice(bytes)
+    }
+}
+
+// `find_completions`/`find_longest_prefix` build a `String` result by concatenating
+// path elements one `char` at a time, so they require `K: Into<char>` (the
+// word/autocomplete use case). Element types with no such conversion, e.g.
+// `String` or `u32`, cannot call them. `u8` does satisfy the bound: each byte
+// becomes the `char` with the same code point, so a `Trie<u8, V>` holding
+// b"abc" yields "abc".
+impl<K, V> Trie<K, V>
+where
+    K: Eq + Hash + Clone + Into<char>,
+{
+    /// Collects every key stored under `prefix`, as concatenated strings.
+    ///
+    /// Walks to the node at `prefix`, then performs a depth-first traversal from
+    /// there, recording a completion each time a terminal node is reached.
+    pub fn find_completions(&self, prefix: impl IntoIterator<Item = K>) -> Vec<String> {
+        let prefix: Vec<K> = prefix.into_iter().collect();
+        let mut completions = Vec::new();
+
+        if let Some(node) = self.find_node(prefix.iter().cloned()) {
+            let mut buffer: String = prefix.into_iter().map(Into::into).collect();
+            node.collect_completions(&mut buffer, &mut completions);
+        }
+
+        completions
+    }
+
+    /// Returns the longest stored key that is a prefix of `query`, if any.
+    pub fn find_longest_prefix(&self, query: impl IntoIterator<Item = K>) -> Option<String> {
+        let mut curr = &self.root;
+        let mut buffer = String::new();
+        let mut longest = None;
+
+        if curr.value.is_some() {
+            longest = Some(buffer.clone());
+        }
+
+        for k in query {
+            match curr.children.get(&k) {
+                Some(child) => {
+                    buffer.push(k.into());
+                    curr = child;
+
+                    if curr.value.is_some() {
+                        longest = Some(buffer.clone());
+                    }
+                }
+                None => break,
+            }
+        }
+
+        longest
+    }
+}
+
+impl<K, V> TrieNode<K, V>
+where
+    K: Eq + Hash + Clone + Into<char>,
+{
+    fn collect_completions(&self, buffer: &mut String, completions: &mut Vec<String>) {
+        if self.value.is_some() {
+            completions.push(buffer.clone());
+        }
+
+        for (k, child) in &self.children {
+            let len = buffer.len();
+            buffer.push(k.clone().into());
+            child.collect_completions(buffer, completions);
+            buffer.truncate(len);
+        }
+    }
+}
+
+impl<K, V> Default for Trie<K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -98,22 +356,154 @@ mod tests {
 
     #[test]
     fn test_trie_insert() {
-        let mut my_trie = Trie::new();
-        let result = my_trie.insert("Hello");
-        assert_eq!(result, "Hello");
+        let mut my_trie: Trie<char, ()> = Trie::new();
+        let result = my_trie.insert("Hello".chars(), ());
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_trie_insert_returns_previous_value() {
+        let mut my_trie: Trie<char, i32> = Trie::new();
+        my_trie.insert("Hello".chars(), 1);
+        let result = my_trie.insert("Hello".chars(), 2);
+        assert_eq!(result, Some(1));
     }
 
     #[test]
     fn test_trie_contains() {
-        let mut my_trie = Trie::new();
-        my_trie.insert("Hello");
-        assert_eq!(my_trie.contains("Hello"), true);
+        let mut my_trie: Trie<char, ()> = Trie::new();
+        my_trie.insert("Hello".chars(), ());
+        assert!(my_trie.contains("Hello".chars()));
     }
 
     #[test]
     fn test_trie_starts_with() {
-        let mut my_trie = Trie::new();
-        my_trie.insert("Hello");
-        assert_eq!(my_trie.starts_with("He"), true);
+        let mut my_trie: Trie<char, ()> = Trie::new();
+        my_trie.insert("Hello".chars(), ());
+        assert!(my_trie.starts_with("He".chars()));
+    }
+
+    #[test]
+    fn test_trie_get_and_get_mut() {
+        let mut my_trie: Trie<char, i32> = Trie::new();
+        my_trie.insert("Hello".chars(), 1);
+
+        assert_eq!(my_trie.get("Hello".chars()), Some(&1));
+        assert_eq!(my_trie.get("He".chars()), None);
+
+        if let Some(value) = my_trie.get_mut("Hello".chars()) {
+            *value += 1;
+        }
+        assert_eq!(my_trie.get("Hello".chars()), Some(&2));
+    }
+
+    #[test]
+    fn test_trie_contains_key() {
+        let mut my_trie: Trie<char, i32> = Trie::new();
+        my_trie.insert("Hello".chars(), 1);
+
+        assert!(my_trie.contains_key("Hello".chars()));
+        assert!(!my_trie.contains_key("He".chars()));
+    }
+
+    #[test]
+    fn test_trie_find_completions() {
+        let mut my_trie: Trie<char, ()> = Trie::new();
+        my_trie.insert("cat".chars(), ());
+        my_trie.insert("car".chars(), ());
+        my_trie.insert("cart".chars(), ());
+        my_trie.insert("dog".chars(), ());
+
+        let mut completions = my_trie.find_completions("ca".chars());
+        completions.sort();
+        assert_eq!(completions, vec!["car", "cart", "cat"]);
+    }
+
+    #[test]
+    fn test_trie_find_longest_prefix() {
+        let mut my_trie: Trie<char, ()> = Trie::new();
+        my_trie.insert("car".chars(), ());
+        my_trie.insert("cart".chars(), ());
+
+        assert_eq!(
+            my_trie.find_longest_prefix("cartographer".chars()),
+            Some("cart".to_string())
+        );
+        assert_eq!(my_trie.find_longest_prefix("cab".chars()), None);
+    }
+
+    #[test]
+    fn test_trie_remove() {
+        let mut my_trie: Trie<char, ()> = Trie::new();
+        my_trie.insert("car".chars(), ());
+        my_trie.insert("cart".chars(), ());
+
+        assert!(my_trie.remove("cart".chars()));
+        assert!(!my_trie.contains("cart".chars()));
+        assert!(my_trie.contains("car".chars()));
+
+        assert!(!my_trie.remove("cart".chars()));
+    }
+
+    #[test]
+    fn test_trie_remove_prunes_empty_branches() {
+        let mut my_trie: Trie<char, ()> = Trie::new();
+        my_trie.insert("cart".chars(), ());
+
+        assert!(my_trie.remove("cart".chars()));
+        assert!(!my_trie.starts_with("ca".chars()));
+        assert!(!my_trie.starts_with("c".chars()));
+    }
+
+    #[test]
+    fn test_trie_search_wildcard() {
+        let mut my_trie: Trie<char, ()> = Trie::new();
+        my_trie.insert("cat".chars(), ());
+        my_trie.insert("car".chars(), ());
+
+        let is_wildcard = |c: &char| *c == '.';
+
+        assert!(my_trie.search_wildcard("c.t".chars(), is_wildcard));
+        assert!(my_trie.search_wildcard("c.r".chars(), is_wildcard));
+        assert!(!my_trie.search_wildcard("c.g".chars(), is_wildcard));
+        assert!(my_trie.search_wildcard("cat".chars(), is_wildcard));
+    }
+
+    #[test]
+    fn test_trie_lookup() {
+        let mut my_trie: Trie<char, ()> = Trie::new();
+        my_trie.insert("car".chars(), ());
+
+        assert_eq!(my_trie.lookup("car".chars()), MatchKind::Exact);
+        assert_eq!(my_trie.lookup("ca".chars()), MatchKind::Prefix);
+        assert_eq!(my_trie.lookup("cab".chars()), MatchKind::Miss);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_trie_of_bytes() {
+        let mut my_trie: Trie<u8, &str> = Trie::new();
+        my_trie.insert(b"abc".iter().copied(), "value");
+        assert!(my_trie.contains(b"abc".iter().copied()));
+        assert!(!my_trie.contains(b"ab".iter().copied()));
+        assert!(my_trie.starts_with(b"ab".iter().copied()));
+
+        // `find_completions`/`find_longest_prefix` require `K: Into<char>`; `u8` satisfies
+        // it, so this trie could call them too, with each byte read as the `char` of the
+        // same code point. This test checks only the key-iterator methods.
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_trie_to_bytes_from_bytes_round_trip() {
+        let mut my_trie: Trie<char, u32> = Trie::new();
+        my_trie.insert("car".chars(), 1);
+        my_trie.insert("cart".chars(), 2);
+
+        let bytes = my_trie.to_bytes().unwrap();
+        let reloaded: Trie<char, u32> = Trie::from_bytes(&bytes).unwrap();
+
+        assert_eq!(reloaded.get("car".chars()), Some(&1));
+        assert_eq!(reloaded.get("cart".chars()), Some(&2));
+        assert!(!reloaded.contains("ca".chars()));
+    }
+}